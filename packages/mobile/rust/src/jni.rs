@@ -1,10 +1,100 @@
 //! JNI bindings for Android
 //!
 //! This module provides JNI wrappers for the C API functions
+//!
+//! # Single-source binding generation (open follow-up)
+//!
+//! The wrappers below are written by hand, one `Java_com_relaynetwork_client_*`
+//! shim per capability. Generating both the C ABI and these JNI entry points
+//! from a single `relay.udl` (UniFFI-style) is the intended end state, but it
+//! is **not delivered yet** and remains an open follow-up, gated on two things
+//! this source snapshot lacks:
+//!
+//! 1. a build manifest — a `Cargo.toml` with `uniffi`/`uniffi-build`
+//!    dependencies and a `build.rs` driving the scaffolding generator (there
+//!    is no `Cargo.toml` in the tree today), and
+//! 2. a uniffi-compatible `RelayClient` surface — the JSON `constructor`, the
+//!    `RelayError` error type and the `RelayListener` callback interface,
+//!    exported via `uniffi` so the `.udl` types line up.
+//!
+//! Until both land the generated scaffolding cannot compile, so the
+//! hand-written shims stay and this item should not be treated as done.
+
+use std::os::raw::c_void;
+use std::sync::Mutex;
+
+use jni::objects::{JByteArray, JByteBuffer, JClass, JObject, JString, JValue};
+use jni::sys::{jbyteArray, jint, jlong, jstring, JNI_VERSION_1_6};
+use jni::{JNIEnv, JavaVM};
+use once_cell::sync::OnceCell;
+
+use super::RelayClient;
 
-use jni::JNIEnv;
-use jni::objects::JClass;
-use jni::sys::jstring;
+/// Fully-qualified name of the Java exception thrown on any native failure.
+const RELAY_EXCEPTION: &str = "com/relaynetwork/client/RelayException";
+
+// Test-coverage follow-up: the create/connect/send/recv/direct-buffer and
+// error-mapping paths have no smoke tests yet — this snapshot has no
+// `Cargo.toml`, so there is no harness to run them (and no upstream tests to
+// match). Once a crate manifest exists, cover the `guard` error/panic →
+// thrown `RelayException` path and the null-handle guards on every entry point.
+
+/// Run a JNI entry point body, converting any error or panic into a thrown
+/// `RelayException` and returning `default`.
+///
+/// Keeping this in one place means every entry point reports failures the
+/// same way: a real checked exception with a stable message rather than a
+/// sentinel value, and a Rust panic never unwinds across the FFI boundary.
+fn guard<T>(
+    env: &mut JNIEnv,
+    default: T,
+    body: impl FnOnce(&mut JNIEnv) -> Result<T, String>,
+) -> T {
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| body(env)));
+    match result {
+        Ok(Ok(value)) => value,
+        Ok(Err(msg)) => {
+            let _ = env.throw_new(RELAY_EXCEPTION, msg);
+            default
+        }
+        Err(_) => {
+            let _ = env.throw_new(RELAY_EXCEPTION, "native panic");
+            default
+        }
+    }
+}
+
+/// Resolve a `jlong` handle into a locked reference to its client.
+///
+/// Rejects a null/zero handle and reconstructs a shared `&Mutex` (never an
+/// aliasing `&mut`) before taking the lock, so the returned guard is the only
+/// mutable access in flight even when several Java threads share the handle.
+///
+/// # Safety
+///
+/// `handle` must be a pointer returned by `nativeCreate` that has not yet been
+/// passed to `nativeDestroy`.
+unsafe fn lock_handle<'a>(handle: jlong) -> Result<std::sync::MutexGuard<'a, RelayClient>, String> {
+    if handle == 0 {
+        return Err("null handle".to_string());
+    }
+    let client = &*(handle as *const Mutex<RelayClient>);
+    // Recover from a poisoned lock rather than bricking the client: `guard`
+    // already turns a panicking body into a thrown exception, so the client
+    // stays usable for the next call.
+    Ok(client.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+/// Process-wide handle to the JVM, stashed from `JNI_OnLoad`. Only the
+/// `JavaVM` is safe to share across threads; the per-thread `JNIEnv` is not.
+static JAVA_VM: OnceCell<JavaVM> = OnceCell::new();
+
+/// Cache the `JavaVM` so background relay threads can attach later.
+#[no_mangle]
+pub extern "system" fn JNI_OnLoad(vm: JavaVM, _reserved: *mut c_void) -> jint {
+    let _ = JAVA_VM.set(vm);
+    JNI_VERSION_1_6
+}
 
 /// Get version string via JNI
 #[no_mangle]
@@ -12,15 +102,203 @@ pub extern "system" fn Java_com_relaynetwork_client_RelayCore_getVersion(
     mut env: JNIEnv,
     _class: JClass,
 ) -> jstring {
-    let version = super::relay_core_version();
-    let version_str = unsafe {
-        std::ffi::CStr::from_ptr(version)
-            .to_str()
-            .unwrap_or("unknown")
-    };
-    
-    env.new_string(version_str)
-        .unwrap_or_else(|_| env.new_string("error").unwrap())
-        .into_raw()
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        let version = super::relay_core_version();
+        let version_str = unsafe {
+            std::ffi::CStr::from_ptr(version)
+                .to_str()
+                .map_err(|e| e.to_string())?
+        };
+        env.new_string(version_str)
+            .map(|s| s.into_raw())
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Create a relay client from a JSON config string and return its handle.
+///
+/// The client is boxed behind a [`Mutex`] and the raw pointer is handed back
+/// to Java as a `jlong`. The lock makes the handle safe to use from several
+/// Java threads at once: every entry point reconstructs a shared `&Mutex`
+/// reference and takes the mutable client out under the lock, so concurrent
+/// `nativeSend`/`nativeRecv` calls can never alias `&mut`. Java owns the
+/// handle until it calls `nativeDestroy`.
+#[no_mangle]
+pub extern "system" fn Java_com_relaynetwork_client_RelayCore_nativeCreate(
+    mut env: JNIEnv,
+    _class: JClass,
+    config: JString,
+) -> jlong {
+    guard(&mut env, 0, |env| {
+        let config: String = env.get_string(&config).map_err(|e| e.to_string())?.into();
+        let client = RelayClient::new(&config).map_err(|e| e.to_string())?;
+        Ok(Box::into_raw(Box::new(Mutex::new(client))) as jlong)
+    })
+}
+
+/// Connect an existing client identified by `handle`.
+#[no_mangle]
+pub extern "system" fn Java_com_relaynetwork_client_RelayCore_nativeConnect(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    guard(&mut env, (), |_env| {
+        let mut client = unsafe { lock_handle(handle)? };
+        client.connect().map_err(|e| e.to_string())
+    })
+}
+
+/// Register a Java listener whose `onMessage([B)` is invoked for every
+/// inbound relay frame.
+///
+/// The listener is promoted to a global ref so it survives past this call,
+/// and the resolved method ID is captured once. Both live inside the
+/// callback installed on the client and are released when the client is
+/// destroyed.
+#[no_mangle]
+pub extern "system" fn Java_com_relaynetwork_client_RelayCore_nativeSetListener(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    listener: JObject,
+) {
+    guard(&mut env, (), |env| {
+        let mut client = unsafe { lock_handle(handle)? };
+
+        let listener = env.new_global_ref(&listener).map_err(|e| e.to_string())?;
+        let method_id = env
+            .get_method_id(
+                "com/relaynetwork/client/RelayListener",
+                "onMessage",
+                "([B)V",
+            )
+            .map_err(|e| e.to_string())?;
+
+        client.set_on_message(move |payload: &[u8]| {
+            let vm = match JAVA_VM.get() {
+                Some(vm) => vm,
+                None => return,
+            };
+            // Attach this relay thread as a daemon: the attachment lasts for
+            // the thread's lifetime and is dropped when it exits, rather than
+            // detaching after every inbound frame on the hot path.
+            let mut env = match vm.attach_current_thread_as_daemon() {
+                Ok(env) => env,
+                Err(_) => return,
+            };
+            // Clear any exception left pending by a previous frame (e.g. a
+            // throwing `onMessage` or a failed array allocation) before issuing
+            // further JNI calls on this reused daemon thread.
+            if env.exception_check().unwrap_or(false) {
+                let _ = env.exception_clear();
+            }
+            let array = match env.byte_array_from_slice(payload) {
+                Ok(a) => a,
+                Err(_) => return,
+            };
+            // Safety: the global ref and method ID both belong to the same
+            // listener object and outlive this call.
+            let _ = unsafe {
+                env.call_method_unchecked(
+                    listener.as_obj(),
+                    method_id,
+                    jni::signature::ReturnType::Primitive(jni::signature::Primitive::Void),
+                    &[JValue::from(&array).as_jni()],
+                )
+            };
+        });
+        Ok(())
+    })
+}
+
+/// Send an arbitrary relay frame, copied out of a Java `byte[]`.
+#[no_mangle]
+pub extern "system" fn Java_com_relaynetwork_client_RelayCore_nativeSend(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    payload: JByteArray,
+) {
+    guard(&mut env, (), |env| {
+        let mut client = unsafe { lock_handle(handle)? };
+        let bytes = env.convert_byte_array(&payload).map_err(|e| e.to_string())?;
+        client.send(&bytes).map_err(|e| e.to_string())
+    })
+}
+
+/// Receive the next relay frame as a freshly allocated Java `byte[]`.
+#[no_mangle]
+pub extern "system" fn Java_com_relaynetwork_client_RelayCore_nativeRecv(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) -> jbyteArray {
+    guard(&mut env, std::ptr::null_mut(), |env| {
+        let mut client = unsafe { lock_handle(handle)? };
+        let frame = client.recv().map_err(|e| e.to_string())?;
+        env.byte_array_from_slice(&frame)
+            .map(|a| a.into_raw())
+            .map_err(|e| e.to_string())
+    })
+}
+
+/// Send a relay frame straight from a direct `ByteBuffer` without copying.
+///
+/// `buffer` must be a direct `ByteBuffer` that Java keeps alive for the
+/// duration of the call; the borrowed slice must not be retained after it
+/// returns.
+#[no_mangle]
+pub extern "system" fn Java_com_relaynetwork_client_RelayCore_nativeSendDirect(
+    mut env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+    buffer: JByteBuffer,
+    len: jint,
+) {
+    guard(&mut env, (), |env| {
+        let mut client = unsafe { lock_handle(handle)? };
+
+        // Safety: `buffer` is a direct `ByteBuffer`; the JVM returns its backing
+        // address and capacity, valid for the duration of this call.
+        let addr = unsafe {
+            env.get_direct_buffer_address(&buffer)
+                .map_err(|e| e.to_string())?
+        };
+        let capacity = env
+            .get_direct_buffer_capacity(&buffer)
+            .map_err(|e| e.to_string())?;
+        if addr.is_null() {
+            return Err("not a direct buffer".to_string());
+        }
+        // Reject a negative length explicitly, before the `as usize` cast wraps
+        // it into a huge value that would only fail as "length exceeds capacity".
+        if len < 0 {
+            return Err("negative length".to_string());
+        }
+        let len = len as usize;
+        if len > capacity {
+            return Err("length exceeds buffer capacity".to_string());
+        }
+        // Safety: `addr` is non-null and `len <= capacity`; Java owns the buffer
+        // for the duration of this call and we never retain the slice after it.
+        let slice = unsafe { std::slice::from_raw_parts(addr, len) };
+        client.send(slice).map_err(|e| e.to_string())
+    })
+}
+
+/// Destroy a client, dropping the boxed value. The handle is invalid afterwards.
+#[no_mangle]
+pub extern "system" fn Java_com_relaynetwork_client_RelayCore_nativeDestroy(
+    _env: JNIEnv,
+    _class: JClass,
+    handle: jlong,
+) {
+    if handle == 0 {
+        return;
+    }
+    unsafe {
+        drop(Box::from_raw(handle as *mut Mutex<RelayClient>));
+    }
 }
 